@@ -0,0 +1,131 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use super::{create_h_tunnel, create_new_room, create_v_tunnel, Map, MapBuilder, Rect, RoomConfig};
+
+// Below this size a rectangle is left as a leaf rather than split again.
+const MIN_RECT_SIZE: u32 = 8;
+// Caps how many rectangles are examined; once it is hit, every rectangle
+// still waiting to be split becomes a leaf as it is.
+const MAX_SPLIT_ITERATIONS: u32 = 240;
+
+/// Carves rooms by recursively splitting the map into rectangles (a binary
+/// space partition) instead of rejecting overlapping rooms. Every leaf
+/// rectangle at least `min_room_size + 2` tiles wide and tall gets a room
+/// (smaller leaves stay empty), so rooms never overlap and no placement is
+/// retried.
+pub struct BspDungeonBuilder {
+  map: Map,
+  room_config: RoomConfig,
+  snapshots: Vec<Map>,
+}
+
+impl BspDungeonBuilder {
+  pub fn new(width: u32, height: u32, room_config: RoomConfig) -> BspDungeonBuilder {
+    BspDungeonBuilder {
+      map: Map::new(width, height),
+      room_config,
+      snapshots: Vec::new(),
+    }
+  }
+
+  fn split_rects(&self, rng: &mut StdRng) -> Vec<Rect> {
+    let mut rects = vec![Rect { x: 2, y: 2, w: self.map.width - 4, h: self.map.height - 4 }];
+    let mut leaves: Vec<Rect> = Vec::new();
+    let mut iterations = 0;
+
+    while let Some(rect) = rects.pop() {
+      iterations += 1;
+      if iterations > MAX_SPLIT_ITERATIONS {
+        leaves.push(rect);
+        continue;
+      }
+
+      let can_split_h = rect.w > MIN_RECT_SIZE * 2;
+      let can_split_v = rect.h > MIN_RECT_SIZE * 2;
+
+      if !can_split_h && !can_split_v {
+        leaves.push(rect);
+        continue;
+      }
+
+      let split_horizontally = if can_split_h && can_split_v {
+        rng.gen::<bool>()
+      } else {
+        can_split_h
+      };
+
+      if split_horizontally {
+        let split_x = rng.gen_range(rect.x + MIN_RECT_SIZE, rect.x + rect.w - MIN_RECT_SIZE);
+        rects.push(Rect { x: rect.x, y: rect.y, w: split_x - rect.x, h: rect.h });
+        rects.push(Rect { x: split_x, y: rect.y, w: rect.x + rect.w - split_x, h: rect.h });
+      } else {
+        let split_y = rng.gen_range(rect.y + MIN_RECT_SIZE, rect.y + rect.h - MIN_RECT_SIZE);
+        rects.push(Rect { x: rect.x, y: rect.y, w: rect.w, h: split_y - rect.y });
+        rects.push(Rect { x: rect.x, y: split_y, w: rect.w, h: rect.y + rect.h - split_y });
+      }
+    }
+
+    leaves
+  }
+}
+
+impl MapBuilder for BspDungeonBuilder {
+  fn build(&mut self, rng: &mut StdRng) {
+    let leaves = self.split_rects(rng);
+    let mut rooms: Vec<Rect> = Vec::new();
+
+    for leaf in leaves.iter() {
+      // Leaf too small to fit even the smallest configured room; skip it.
+      if leaf.w < self.room_config.min_room_size + 2 || leaf.h < self.room_config.min_room_size + 2 {
+        continue;
+      }
+
+      let max_w = (leaf.w - 2).min(self.room_config.max_room_size);
+      let max_h = (leaf.h - 2).min(self.room_config.max_room_size);
+
+      let w = rng.gen_range(self.room_config.min_room_size, max_w + 1);
+      let h = rng.gen_range(self.room_config.min_room_size, max_h + 1);
+
+      let x = rng.gen_range(leaf.x + 1, leaf.x + leaf.w - w);
+      let y = rng.gen_range(leaf.y + 1, leaf.y + leaf.h - h);
+
+      let room = Rect { x, y, w, h };
+      create_new_room(&mut self.map, &room);
+      rooms.push(room);
+      self.take_snapshot();
+    }
+
+    // Connect each room to the next, same as SimpleMapBuilder.
+    for (index, room) in rooms.iter().enumerate() {
+      if index < rooms.len() - 1 {
+        let current_center = room.center();
+        let next_center = rooms[index + 1].center();
+
+        if rng.gen::<bool>() {
+          create_h_tunnel(&mut self.map, &current_center.x, &next_center.x, &current_center.y);
+          create_v_tunnel(&mut self.map, &current_center.y, &next_center.y, &next_center.x);
+        } else {
+          create_v_tunnel(&mut self.map, &current_center.y, &next_center.y, &current_center.x);
+          create_h_tunnel(&mut self.map, &current_center.x, &next_center.x, &next_center.y);
+        }
+
+        self.take_snapshot();
+      }
+    }
+
+    self.map.rooms = rooms;
+  }
+
+  fn get_map(&self) -> Map {
+    self.map.clone()
+  }
+
+  fn get_snapshot_history(&self) -> &Vec<Map> {
+    &self.snapshots
+  }
+
+  fn snapshots_mut(&mut self) -> &mut Vec<Map> {
+    &mut self.snapshots
+  }
+}
@@ -0,0 +1,107 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use super::{Map, MapBuilder, Wall};
+
+const INITIAL_WALL_CHANCE: f64 = 0.45;
+const SMOOTHING_ITERATIONS: u32 = 5;
+const WALL_NEIGHBOR_THRESHOLD: u32 = 5;
+
+/// Produces organic caverns instead of rectilinear rooms: seed the map with
+/// random noise, then repeatedly smooth it so dense pockets of wall survive
+/// as cave walls and everything else opens into floor.
+pub struct CellularAutomataBuilder {
+  map: Map,
+  snapshots: Vec<Map>,
+}
+
+impl CellularAutomataBuilder {
+  pub fn new(width: u32, height: u32) -> CellularAutomataBuilder {
+    CellularAutomataBuilder { map: Map::new(width, height), snapshots: Vec::new() }
+  }
+
+  fn apply_walls(&mut self, walls: &[bool]) {
+    for y in 0..self.map.height {
+      for x in 0..self.map.width {
+        let idx = self.map.xy_idx(x, y);
+
+        if walls[idx] {
+          self.map.tiles[idx].empty = false;
+          self.map.tiles[idx].sprite_type = None;
+        } else {
+          self.map.tiles[idx].floor();
+          self.map.tiles[idx].wall(Wall::Floor);
+        }
+      }
+    }
+  }
+
+  fn count_wall_neighbors(&self, walls: &[bool], x: u32, y: u32) -> u32 {
+    let mut count = 0;
+
+    for dy in -1..=1i32 {
+      for dx in -1..=1i32 {
+        if dx == 0 && dy == 0 {
+          continue;
+        }
+
+        let nx = x as i32 + dx;
+        let ny = y as i32 + dy;
+        let out_of_bounds = nx < 0 || ny < 0 || nx >= self.map.width as i32 || ny >= self.map.height as i32;
+
+        if out_of_bounds || walls[self.map.xy_idx(nx as u32, ny as u32)] {
+          count += 1;
+        }
+      }
+    }
+
+    count
+  }
+}
+
+impl MapBuilder for CellularAutomataBuilder {
+  fn build(&mut self, rng: &mut StdRng) {
+    let size = (self.map.width * self.map.height) as usize;
+    let mut walls = vec![true; size];
+
+    // Seed the interior with noise; the outermost ring stays wall (its
+    // `true` from the initial fill above is never overwritten) so caves
+    // never open onto the map edge.
+    for y in 1..self.map.height - 1 {
+      for x in 1..self.map.width - 1 {
+        walls[self.map.xy_idx(x, y)] = rng.gen_bool(INITIAL_WALL_CHANCE);
+      }
+    }
+
+    self.apply_walls(&walls);
+    self.take_snapshot();
+
+    for _ in 0..SMOOTHING_ITERATIONS {
+      let mut next = walls.clone();
+
+      for y in 1..self.map.height - 1 {
+        for x in 1..self.map.width - 1 {
+          let wall_neighbors = self.count_wall_neighbors(&walls, x, y);
+          let idx = self.map.xy_idx(x, y);
+          next[idx] = wall_neighbors >= WALL_NEIGHBOR_THRESHOLD;
+        }
+      }
+
+      walls = next;
+      self.apply_walls(&walls);
+      self.take_snapshot();
+    }
+  }
+
+  fn get_map(&self) -> Map {
+    self.map.clone()
+  }
+
+  fn get_snapshot_history(&self) -> &Vec<Map> {
+    &self.snapshots
+  }
+
+  fn snapshots_mut(&mut self) -> &mut Vec<Map> {
+    &mut self.snapshots
+  }
+}
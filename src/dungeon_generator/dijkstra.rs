@@ -0,0 +1,68 @@
+use std::collections::VecDeque;
+
+use super::{Map, Wall};
+
+/// Post-processing pass any `MapBuilder`'s output can run through: flood
+/// fill from `start` over floor tiles recording step distance, turn any
+/// floor tile the flood never reached back into wall (so isolated pockets
+/// left by tunnel-joining vanish), and mark the reachable tile farthest
+/// from `start` as the level exit.
+pub fn cull_unreachable_and_place_exit(map: &mut Map, start: (u32, u32)) {
+  let mut distances: Vec<Option<u32>> = vec![None; map.tiles.len()];
+  let start_idx = map.xy_idx(start.0, start.1);
+  distances[start_idx] = Some(0);
+
+  let mut queue = VecDeque::new();
+  queue.push_back(start_idx);
+
+  while let Some(idx) = queue.pop_front() {
+    let dist = distances[idx].unwrap();
+    let x = idx as u32 % map.width;
+    let y = idx as u32 / map.width;
+
+    let mut neighbors = Vec::new();
+    if x > 0 {
+      neighbors.push((x - 1, y));
+    }
+    if x + 1 < map.width {
+      neighbors.push((x + 1, y));
+    }
+    if y > 0 {
+      neighbors.push((x, y - 1));
+    }
+    if y + 1 < map.height {
+      neighbors.push((x, y + 1));
+    }
+
+    for (nx, ny) in neighbors {
+      let nidx = map.xy_idx(nx, ny);
+      if map.tiles[nidx].empty && distances[nidx].is_none() {
+        distances[nidx] = Some(dist + 1);
+        queue.push_back(nidx);
+      }
+    }
+  }
+
+  // Floor tiles the flood never reached are dead pockets; wall them off.
+  for (idx, tile) in map.tiles.iter_mut().enumerate() {
+    if tile.empty && distances[idx].is_none() {
+      tile.empty = false;
+      tile.sprite_type = None;
+    }
+  }
+
+  let exit_idx = distances
+    .iter()
+    .enumerate()
+    .filter_map(|(idx, dist)| dist.map(|dist| (idx, dist)))
+    .max_by_key(|(_, dist)| *dist)
+    .map(|(idx, _)| idx);
+
+  if let Some(exit_idx) = exit_idx {
+    map.tiles[exit_idx].wall(Wall::DownStairs);
+    map.exit = (exit_idx as u32 % map.width, exit_idx as u32 / map.width);
+  }
+
+  map.start = start;
+  map.distances = distances;
+}
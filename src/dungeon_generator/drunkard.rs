@@ -0,0 +1,90 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use super::{Map, MapBuilder, Wall};
+
+const TARGET_FLOOR_PERCENT: f64 = 0.4;
+const MAX_STEPS_BEFORE_TELEPORT: u32 = 200;
+
+/// Carves winding, guaranteed-connected tunnels by walking a single digger
+/// around the map: every step moves from the current floor tile onto an
+/// adjacent one, so there's never a gap for the walker to leave behind.
+pub struct DrunkardsWalkBuilder {
+  map: Map,
+  snapshots: Vec<Map>,
+}
+
+impl DrunkardsWalkBuilder {
+  pub fn new(width: u32, height: u32) -> DrunkardsWalkBuilder {
+    DrunkardsWalkBuilder { map: Map::new(width, height), snapshots: Vec::new() }
+  }
+}
+
+impl MapBuilder for DrunkardsWalkBuilder {
+  fn build(&mut self, rng: &mut StdRng) {
+    // The digger never steps onto the outer ring (see `in_bounds` below), so
+    // only the interior is carveable; size the target against that area
+    // rather than the full grid or small maps would spin forever.
+    let interior_tiles = ((self.map.width.saturating_sub(2)) * (self.map.height.saturating_sub(2))) as f64;
+    let target_floor_tiles = (interior_tiles * TARGET_FLOOR_PERCENT) as usize;
+
+    let mut floor_tiles: Vec<(u32, u32)> = Vec::new();
+    let mut digger_x = self.map.width / 2;
+    let mut digger_y = self.map.height / 2;
+    let mut steps_since_teleport = 0;
+
+    loop {
+      let idx = self.map.xy_idx(digger_x, digger_y);
+      if self.map.tiles[idx].sprite_type.is_none() {
+        self.map.tiles[idx].floor();
+        self.map.tiles[idx].wall(Wall::Floor);
+        floor_tiles.push((digger_x, digger_y));
+      }
+
+      if floor_tiles.len() >= target_floor_tiles {
+        break;
+      }
+
+      steps_since_teleport += 1;
+
+      let (dx, dy): (i32, i32) = match rng.gen_range(0, 4) {
+        0 => (0, -1),
+        1 => (0, 1),
+        2 => (-1, 0),
+        _ => (1, 0),
+      };
+
+      let next_x = digger_x as i32 + dx;
+      let next_y = digger_y as i32 + dy;
+      let in_bounds = next_x > 0 && next_y > 0
+        && next_x < self.map.width as i32 - 1
+        && next_y < self.map.height as i32 - 1;
+
+      if in_bounds && steps_since_teleport < MAX_STEPS_BEFORE_TELEPORT {
+        digger_x = next_x as u32;
+        digger_y = next_y as u32;
+      } else {
+        // Wandered off the map or stalled; resume from an already-carved tile.
+        let (tx, ty) = floor_tiles[rng.gen_range(0, floor_tiles.len())];
+        digger_x = tx;
+        digger_y = ty;
+        steps_since_teleport = 0;
+        self.take_snapshot();
+      }
+    }
+
+    self.take_snapshot();
+  }
+
+  fn get_map(&self) -> Map {
+    self.map.clone()
+  }
+
+  fn get_snapshot_history(&self) -> &Vec<Map> {
+    &self.snapshots
+  }
+
+  fn snapshots_mut(&mut self) -> &mut Vec<Map> {
+    &mut self.snapshots
+  }
+}
@@ -0,0 +1,106 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use super::{Map, MapBuilder, Wall};
+
+/// Generates a maze with recursive backtracking, then knocks out a few extra
+/// walls so it is not strictly loop-free. Cells live on odd tile coordinates
+/// and the even coordinates between them are the walls a step can knock out
+/// (the outer ring stays wall), so a (map.width - 1) / 2 by
+/// (map.height - 1) / 2 grid of cells is addressable at half the map's
+/// resolution.
+pub struct MazeBuilder {
+  map: Map,
+  snapshots: Vec<Map>,
+}
+
+impl MazeBuilder {
+  pub fn new(width: u32, height: u32) -> MazeBuilder {
+    MazeBuilder { map: Map::new(width, height), snapshots: Vec::new() }
+  }
+
+  fn carve(&mut self, x: u32, y: u32) {
+    let idx = self.map.xy_idx(x, y);
+    self.map.tiles[idx].floor();
+    self.map.tiles[idx].wall(Wall::Floor);
+  }
+}
+
+impl MapBuilder for MazeBuilder {
+  fn build(&mut self, rng: &mut StdRng) {
+    let cell_cols = (self.map.width - 1) / 2;
+    let cell_rows = (self.map.height - 1) / 2;
+    let cell_idx = |cx: u32, cy: u32| (cy * cell_cols + cx) as usize;
+
+    let mut visited = vec![false; (cell_cols * cell_rows) as usize];
+    let mut stack = vec![(0u32, 0u32)];
+    visited[cell_idx(0, 0)] = true;
+    self.carve(1, 1);
+
+    while let Some(&(cx, cy)) = stack.last() {
+      let mut neighbors: Vec<(u32, u32)> = Vec::new();
+
+      if cx > 0 && !visited[cell_idx(cx - 1, cy)] {
+        neighbors.push((cx - 1, cy));
+      }
+      if cx + 1 < cell_cols && !visited[cell_idx(cx + 1, cy)] {
+        neighbors.push((cx + 1, cy));
+      }
+      if cy > 0 && !visited[cell_idx(cx, cy - 1)] {
+        neighbors.push((cx, cy - 1));
+      }
+      if cy + 1 < cell_rows && !visited[cell_idx(cx, cy + 1)] {
+        neighbors.push((cx, cy + 1));
+      }
+
+      if neighbors.is_empty() {
+        stack.pop();
+        continue;
+      }
+
+      let (nx, ny) = neighbors[rng.gen_range(0, neighbors.len())];
+
+      // Knock out the wall between the current cell and its neighbor, then
+      // carve the neighbor cell itself. The wall always sits one tile
+      // toward the neighbor from the current cell.
+      let wall_x = (cx * 2 + 1) as i32 + (nx as i32 - cx as i32);
+      let wall_y = (cy * 2 + 1) as i32 + (ny as i32 - cy as i32);
+      self.carve(wall_x as u32, wall_y as u32);
+      self.carve(nx * 2 + 1, ny * 2 + 1);
+
+      visited[cell_idx(nx, ny)] = true;
+      stack.push((nx, ny));
+      self.take_snapshot();
+    }
+
+    // Knock out a handful of extra walls so the maze isn't perfectly
+    // loop-free; purely cosmetic sparsification.
+    let extra_openings = (cell_cols * cell_rows) / 20;
+    for _ in 0..extra_openings {
+      let cx = rng.gen_range(0, cell_cols);
+      let cy = rng.gen_range(0, cell_rows);
+
+      if rng.gen::<bool>() {
+        if cx + 1 < cell_cols {
+          self.carve(cx * 2 + 2, cy * 2 + 1);
+        }
+      } else if cy + 1 < cell_rows {
+        self.carve(cx * 2 + 1, cy * 2 + 2);
+      }
+    }
+
+    self.take_snapshot();
+  }
+
+  fn get_map(&self) -> Map {
+    self.map.clone()
+  }
+
+  fn get_snapshot_history(&self) -> &Vec<Map> {
+    &self.snapshots
+  }
+
+  fn snapshots_mut(&mut self) -> &mut Vec<Map> {
+    &mut self.snapshots
+  }
+}
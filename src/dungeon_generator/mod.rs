@@ -0,0 +1,287 @@
+use std::cmp::{min, max};
+
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+mod simple;
+pub use simple::SimpleMapBuilder;
+
+mod bsp;
+pub use bsp::BspDungeonBuilder;
+
+mod cellular_automata;
+pub use cellular_automata::CellularAutomataBuilder;
+
+mod drunkard;
+pub use drunkard::DrunkardsWalkBuilder;
+
+mod maze;
+pub use maze::MazeBuilder;
+
+mod dijkstra;
+pub use dijkstra::cull_unreachable_and_place_exit;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Wall {
+  Left,
+  Right,
+  Top,
+  Bottom,
+  Floor,
+  DownStairs,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Tile {
+  pub x: u32,
+  pub y: u32,
+  pub empty: bool,
+  pub sprite_type: Option<Wall>,
+}
+
+impl Tile {
+  pub fn floor(&mut self) {
+    self.empty = true;
+  }
+
+  pub fn wall(&mut self, wall: Wall) {
+    self.sprite_type = Some(wall);
+  }
+
+  pub fn north(&self) -> u32 {
+    self.y + 1
+  }
+
+  pub fn south(&self) -> u32 {
+    self.y - 1
+  }
+
+  pub fn east(&self) -> u32 {
+    self.x + 1
+  }
+
+  pub fn west(&self) -> u32 {
+    self.x - 1
+  }
+}
+
+pub struct Point {
+  pub x: u32,
+  pub y: u32,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Rect {
+  pub x: u32,
+  pub y: u32,
+  pub w: u32,
+  pub h: u32,
+}
+
+impl Rect {
+  pub fn intersects_with(&self, rect: &Rect) -> bool {
+    (self.x <= (rect.x + rect.w)) &&
+    ((self.x + self.w) >= rect.x) &&
+    (self.y <= (rect.y + rect.h)) &&
+    ((self.y + self.h) >= rect.y)
+  }
+
+  pub fn center(&self) -> Point {
+    let center_x = (self.x + (self.x + self.w)) / 2;
+    let center_y = (self.y + (self.y + self.h)) / 2;
+
+    Point { x: center_x, y: center_y }
+  }
+}
+
+pub struct RoomConfig {
+  pub max_room_size: u32,
+  pub min_room_size: u32,
+  pub max_rooms: u32,
+  pub min_rooms: u32,
+}
+
+/// A generated level: the tile grid, the rooms carved into it, and the
+/// dimensions needed to translate between them. `xy_idx` is the single
+/// place that turns (x, y) into a tile index, so every builder agrees on
+/// the same row-major layout.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Map {
+  pub tiles: Vec<Tile>,
+  pub rooms: Vec<Rect>,
+  pub width: u32,
+  pub height: u32,
+  /// Step distance from `start` to each tile, filled in by
+  /// `cull_unreachable_and_place_exit`. `None` until that pass runs, and
+  /// `None` afterwards for any tile that turned out to be unreachable.
+  pub distances: Vec<Option<u32>>,
+  pub start: (u32, u32),
+  pub exit: (u32, u32),
+}
+
+impl Map {
+  pub fn new(width: u32, height: u32) -> Map {
+    let size = (width * height) as usize;
+    let mut tiles = Vec::with_capacity(size);
+
+    for y in 0..height {
+      for x in 0..width {
+        tiles.push(Tile { x, y, empty: false, sprite_type: None });
+      }
+    }
+
+    Map {
+      tiles,
+      rooms: Vec::new(),
+      width,
+      height,
+      distances: vec![None; size],
+      start: (0, 0),
+      exit: (0, 0),
+    }
+  }
+
+  pub fn xy_idx(&self, x: u32, y: u32) -> usize {
+    (y * self.width + x) as usize
+  }
+
+  /// Persists the map as RON so it can be shared or regenerated later with
+  /// `Map::load`.
+  pub fn save(&self, path: &str) -> std::io::Result<()> {
+    let serialized = ron::ser::to_string(self)
+      .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    std::fs::write(path, serialized)
+  }
+
+  pub fn load(path: &str) -> std::io::Result<Map> {
+    let contents = std::fs::read_to_string(path)?;
+    ron::de::from_str(&contents).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+  }
+}
+
+/// Implemented by each map generation algorithm. `build` mutates the
+/// builder's internal map, then `get_map` hands back the finished result
+/// so `main` doesn't need to know which algorithm produced it.
+pub trait MapBuilder {
+  fn build(&mut self, rng: &mut StdRng);
+  fn get_map(&self) -> Map;
+
+  /// Every snapshot taken during `build`, in order, for `--visualize`.
+  fn get_snapshot_history(&self) -> &Vec<Map>;
+  fn snapshots_mut(&mut self) -> &mut Vec<Map>;
+
+  /// Records the current map state. Call this after a meaningful build
+  /// step (a room carved, a tunnel dug, a smoothing pass finished).
+  fn take_snapshot(&mut self) {
+    let snapshot = self.get_map();
+    self.snapshots_mut().push(snapshot);
+  }
+}
+
+/// Picks a builder by name (as read from `config.ron`), falling back to
+/// the original room-and-tunnel algorithm for unrecognized names.
+pub fn build_map_builder(name: &str, width: u32, height: u32, room_config: RoomConfig) -> Box<dyn MapBuilder> {
+  match name {
+    "bsp" => Box::new(BspDungeonBuilder::new(width, height, room_config)),
+    "cellular_automata" => Box::new(CellularAutomataBuilder::new(width, height)),
+    "drunkards_walk" => Box::new(DrunkardsWalkBuilder::new(width, height)),
+    "maze" => Box::new(MazeBuilder::new(width, height)),
+    _ => Box::new(SimpleMapBuilder::new(width, height, room_config)),
+  }
+}
+
+pub fn create_new_room(map: &mut Map, room: &Rect) {
+  for x in room.x..room.x + room.w {
+    for y in room.y..room.y + room.h {
+      let index = map.xy_idx(x, y);
+      map.tiles[index].floor();
+      map.tiles[index].wall(Wall::Floor);
+
+      if (room.x + room.w < map.width) && room.x > 0 && room.y > 0 && (room.y + room.h < map.height) {
+        if map.tiles[index].north() == (room.y + room.h) {
+          map.tiles[index].wall(Wall::Top);
+        } else if map.tiles[index].south() == (room.y - 1) {
+          map.tiles[index].wall(Wall::Bottom);
+        }
+
+        if map.tiles[index].east() == (room.x + room.w) {
+          map.tiles[index].wall(Wall::Right);
+        } else if map.tiles[index].west() == (room.x - 1) {
+          map.tiles[index].wall(Wall::Left);
+        }
+      }
+    }
+  }
+}
+
+pub fn create_h_tunnel(map: &mut Map, x1: &u32, x2: &u32, y: &u32) {
+  let min_x: u32 = min(*x1, *x2);
+  let max_x: u32 = max(*x1, *x2);
+
+  for x in min_x..max_x {
+    let index = map.xy_idx(x, *y);
+    map.tiles[index].floor();
+    map.tiles[index].wall(Wall::Floor);
+
+    let north_tile_index = map.tiles[index].north();
+    let south_tile_index = map.tiles[index].south();
+    let east_tile_index = map.tiles[index].east();
+    let west_tile_index = map.tiles[index].west();
+
+    let east_idx = map.xy_idx(east_tile_index, *y);
+    if x == min_x && map.tiles[east_idx].sprite_type.is_none() {
+      map.tiles[east_idx].wall(Wall::Top);
+    }
+
+    let west_idx = map.xy_idx(west_tile_index, *y);
+    if x == min_x && map.tiles[west_idx].sprite_type.is_none() {
+      map.tiles[west_idx].wall(Wall::Top);
+    }
+
+    let north_idx = map.xy_idx(x, north_tile_index);
+    match map.tiles[north_idx].sprite_type {
+      Some(Wall::Bottom) | Some(Wall::Right) | Some(Wall::Left) | None => {
+        map.tiles[north_idx].wall(Wall::Top);
+      },
+      _ => {},
+    }
+
+    let south_idx = map.xy_idx(x, south_tile_index);
+    match map.tiles[south_idx].sprite_type {
+      Some(Wall::Bottom) | Some(Wall::Right) | Some(Wall::Left) | None => {
+        map.tiles[south_idx].wall(Wall::Bottom);
+      },
+      _ => {},
+    }
+  }
+}
+
+pub fn create_v_tunnel(map: &mut Map, y1: &u32, y2: &u32, x: &u32) {
+  let min_y: u32 = min(*y1, *y2);
+  let max_y: u32 = max(*y1, *y2);
+
+  for y in min_y..max_y {
+    let index = map.xy_idx(*x, y);
+    map.tiles[index].floor();
+    map.tiles[index].wall(Wall::Floor);
+
+    let east_tile_index = map.tiles[index].east();
+    let west_tile_index = map.tiles[index].west();
+
+    let east_idx = map.xy_idx(east_tile_index, y);
+    match map.tiles[east_idx].sprite_type {
+      Some(Wall::Right) | Some(Wall::Left) | None => {
+        map.tiles[east_idx].wall(Wall::Right);
+      },
+      _ => {},
+    }
+
+    let west_idx = map.xy_idx(west_tile_index, y);
+    match map.tiles[west_idx].sprite_type {
+      Some(Wall::Right) | Some(Wall::Left) | None => {
+        map.tiles[west_idx].wall(Wall::Left);
+      },
+      _ => {},
+    }
+  }
+}
@@ -0,0 +1,91 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use super::{create_h_tunnel, create_new_room, create_v_tunnel, Map, MapBuilder, Rect, RoomConfig};
+
+/// The original random-room-and-tunnel algorithm: keep proposing rectangles
+/// until enough of them land without overlapping, then join each room to
+/// the next with an L-shaped tunnel.
+pub struct SimpleMapBuilder {
+  map: Map,
+  room_config: RoomConfig,
+  snapshots: Vec<Map>,
+}
+
+impl SimpleMapBuilder {
+  pub fn new(width: u32, height: u32, room_config: RoomConfig) -> SimpleMapBuilder {
+    SimpleMapBuilder {
+      map: Map::new(width, height),
+      room_config,
+      snapshots: Vec::new(),
+    }
+  }
+}
+
+impl MapBuilder for SimpleMapBuilder {
+  fn build(&mut self, rng: &mut StdRng) {
+    let num_rooms = rng.gen_range(self.room_config.min_rooms, self.room_config.max_rooms + 1);
+    let mut generated_rooms: Vec<Rect> = Vec::with_capacity(num_rooms as usize);
+
+    loop {
+      // random width and height
+      let w = rng.gen_range(self.room_config.min_room_size, self.room_config.max_room_size + 1);
+      let h = rng.gen_range(self.room_config.min_room_size, self.room_config.max_room_size + 1);
+
+      // random position without going out of the boundaries of the map
+      let bounds_x = self.map.width - w - 3;
+      let bounds_y = self.map.height - h - 3;
+      let x = rng.gen_range(1, bounds_x);
+      let y = rng.gen_range(1, bounds_y);
+
+      // create the room and check if it intersects with already existing rooms. If it
+      // doesn't, store it.
+      let new_room = Rect { x, y, w, h };
+      let intersects = generated_rooms.iter().any(|room| new_room.intersects_with(room));
+
+      if !intersects {
+        create_new_room(&mut self.map, &new_room);
+        generated_rooms.push(new_room);
+        self.take_snapshot();
+      }
+
+      if generated_rooms.len() == num_rooms as usize {
+        break;
+      }
+    }
+
+    // Create tunnels between rooms.
+    for (index, room) in generated_rooms.iter().enumerate() {
+      if index < generated_rooms.len() - 1 {
+        let current_center = room.center();
+        let prev_center = generated_rooms[index + 1].center();
+
+        if rng.gen::<bool>() {
+          // draw a horizontal corridor first, then vertical
+          create_h_tunnel(&mut self.map, &prev_center.x, &current_center.x, &prev_center.y);
+          create_v_tunnel(&mut self.map, &prev_center.y, &current_center.y, &current_center.x);
+        } else {
+          // draw a vertical corridor first, then horizontal
+          create_v_tunnel(&mut self.map, &prev_center.y, &current_center.y, &prev_center.x);
+          create_h_tunnel(&mut self.map, &prev_center.x, &current_center.x, &current_center.y);
+        }
+
+        self.take_snapshot();
+      }
+    }
+
+    self.map.rooms = generated_rooms;
+  }
+
+  fn get_map(&self) -> Map {
+    self.map.clone()
+  }
+
+  fn get_snapshot_history(&self) -> &Vec<Map> {
+    &self.snapshots
+  }
+
+  fn snapshots_mut(&mut self) -> &mut Vec<Map> {
+    &mut self.snapshots
+  }
+}
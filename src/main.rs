@@ -2,12 +2,15 @@ use std::env;
 use std::path::Path;
 use std::{fs::File};
 
-use image::{ImageBuffer, GenericImageView};
+use image::codecs::gif::GifEncoder;
+use image::{DynamicImage, Frame, GenericImageView, ImageBuffer, Rgba};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use ron::de::{from_reader};
 use serde::Deserialize;
 
 mod dungeon_generator;
-use crate::dungeon_generator::{create_map, RoomConfig, Wall};
+use crate::dungeon_generator::{build_map_builder, cull_unreachable_and_place_exit, Map, RoomConfig, Wall};
 
 #[derive(Debug, Deserialize)]
 struct Config {
@@ -18,21 +21,53 @@ struct Config {
     wall_tile_v_right: (u32, u32),
     wall_tile_v_left: (u32, u32),
     floor_tile: (u32, u32),
+    #[serde(default)]
+    down_stairs_tile: (u32, u32),
     max_room_size: u32,
     min_room_size: u32,
     max_rooms: u32,
     min_rooms: u32,
+    #[serde(default = "default_builder")]
+    builder: String,
+    #[serde(default)]
+    visualize: bool,
+}
+
+/// Pre-existing `config.ron` files predate the `builder` field; fall back to
+/// the original room-and-tunnel algorithm so they keep working unchanged.
+fn default_builder() -> String {
+  "simple".to_string()
 }
 
 fn main() {
-    let file = if env::args().count() == 2 {
-        env::args().nth(1).unwrap()
-    } else {
-        panic!("Please enter a file")
-    };
+    let mut file: Option<String> = None;
+    let mut visualize = false;
+    let mut seed: Option<u64> = None;
+    let mut load_path: Option<String> = None;
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--visualize" => visualize = true,
+            "--seed" => {
+                i += 1;
+                let value = args.get(i).expect("--seed requires a value");
+                seed = Some(value.parse().expect("--seed value must be a number"));
+            },
+            "--load" => {
+                i += 1;
+                load_path = Some(args.get(i).expect("--load requires a path").clone());
+            },
+            arg => file = Some(arg.to_string()),
+        }
+        i += 1;
+    }
+
+    let file = file.expect("Please enter a file");
 
     // Read config file to build image.
-    let config_file = File::open(&Path::new("config.ron")).expect("Failed opening config file");
+    let config_file = File::open(Path::new("config.ron")).expect("Failed opening config file");
     let config: Config = match from_reader(config_file) {
       Ok(c) => c,
       Err(e) => {
@@ -45,14 +80,6 @@ fn main() {
     let imgx = config.width;
     let imgy = config.height;
     let tile_size = config.tile_size;
-    let wall_tile_h_x = config.wall_tile_h.0;
-    let wall_tile_h_y = config.wall_tile_h.1;
-    let wall_tile_vr_x = config.wall_tile_v_right.0;
-    let wall_tile_vr_y = config.wall_tile_v_right.1;
-    let wall_tile_vl_x = config.wall_tile_v_left.0;
-    let wall_tile_vl_y = config.wall_tile_v_left.1;
-    let floor_tile_x = config.floor_tile.0;
-    let floor_tile_y = config.floor_tile.1;
 
     let room_config = RoomConfig {
       max_room_size: config.max_room_size,
@@ -61,14 +88,91 @@ fn main() {
       min_rooms: config.min_rooms,
     };
 
-    let tiles = create_map(&imgx, &imgy, &tile_size, &room_config);
+    let map_width = imgx / tile_size;
+    let map_height = imgy / tile_size;
+
+    let texture = image::open(Path::new(&file)).unwrap();
+
+    let map = if let Some(load_path) = load_path {
+      // A saved map reproduces exactly, so there's no build to visualize.
+      match Map::load(&load_path) {
+        Ok(map) => map,
+        Err(e) => {
+          println!("Failed to load map: {}", e);
+
+          std::process::exit(1);
+        }
+      }
+    } else {
+      let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+      };
+      let mut builder = build_map_builder(&config.builder, map_width, map_height, room_config);
+      builder.build(&mut rng);
+
+      // When --visualize is set (or config.visualize), render every recorded
+      // build step as a GIF frame instead of just the finished map.
+      if visualize || config.visualize {
+        let frames: Vec<Frame> = builder
+          .get_snapshot_history()
+          .iter()
+          .map(|snapshot| Frame::new(render_map(snapshot, &texture, &config, imgx, imgy, tile_size)))
+          .collect();
+
+        let gif_file = File::create("output.gif").unwrap();
+        let mut encoder = GifEncoder::new(gif_file);
+        encoder.encode_frames(frames).expect("Failed to encode GIF");
+
+        return;
+      }
+
+      let mut map = builder.get_map();
+
+      let start = match map.rooms.first() {
+        Some(room) => {
+          let center = room.center();
+          (center.x, center.y)
+        },
+        // Roomless builders (cellular automata, maze, drunkard's walk) have
+        // no room center to start from, and the geometric center itself
+        // isn't guaranteed to be floor; start from the first floor tile in
+        // row-major order so the flood fill in cull_unreachable_and_place_exit
+        // starts on floor instead of on a wall. Where the floor forms several
+        // disconnected regions, only the region holding that tile is kept.
+        None => map
+          .tiles
+          .iter()
+          .find(|tile| tile.empty)
+          .map(|tile| (tile.x, tile.y))
+          .unwrap_or((map_width / 2, map_height / 2)),
+      };
+      cull_unreachable_and_place_exit(&mut map, start);
+
+      map
+    };
+
+    map.save("map.ron").expect("Failed to save map");
+
+    let imgbuf = render_map(&map, &texture, &config, imgx, imgy, tile_size);
+
+    // Save the image as output.png
+    imgbuf.save("output.png").unwrap()
+}
 
-    // Create a new ImgBuf with width: imgx and height: imgy
+// Draws a map through the sprite-mapping table in `config`; shared by the
+// single-image path and each frame of the --visualize GIF.
+fn render_map(
+    map: &Map,
+    texture: &DynamicImage,
+    config: &Config,
+    imgx: u32,
+    imgy: u32,
+    tile_size: u32,
+) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
     let mut imgbuf = ImageBuffer::new(imgx, imgy);
-    let texture = image::open(&Path::new(&file)).unwrap();
 
-    // Draw the map
-    for tile in tiles {
+    for tile in &map.tiles {
       let x_pixel_offset = tile.x * tile_size;
       let y_pixel_offset = tile.y * tile_size;
 
@@ -78,27 +182,30 @@ fn main() {
           let mut sprite_x = 0;
           let mut sprite_y = 0;
 
-          // println!("sprite type: {:?}", tile.sprite_type);
           match tile.sprite_type {
             Some(Wall::Top) => {
-              sprite_x = wall_tile_h_x;
-              sprite_y = wall_tile_h_y;
+              sprite_x = config.wall_tile_h.0;
+              sprite_y = config.wall_tile_h.1;
             },
             Some(Wall::Right) => {
-              sprite_x = wall_tile_vr_x;
-              sprite_y = wall_tile_vr_y;
+              sprite_x = config.wall_tile_v_right.0;
+              sprite_y = config.wall_tile_v_right.1;
             },
             Some(Wall::Bottom) => {
-              sprite_x = wall_tile_h_x;
-              sprite_y = wall_tile_h_y;
+              sprite_x = config.wall_tile_h.0;
+              sprite_y = config.wall_tile_h.1;
             },
             Some(Wall::Left) => {
-              sprite_x = wall_tile_vl_x;
-              sprite_y = wall_tile_vl_y;
+              sprite_x = config.wall_tile_v_left.0;
+              sprite_y = config.wall_tile_v_left.1;
             },
             Some(Wall::Floor) => {
-              sprite_x = floor_tile_x;
-              sprite_y = floor_tile_y;
+              sprite_x = config.floor_tile.0;
+              sprite_y = config.floor_tile.1;
+            }
+            Some(Wall::DownStairs) => {
+              sprite_x = config.down_stairs_tile.0;
+              sprite_y = config.down_stairs_tile.1;
             }
             None => {},
           };
@@ -106,12 +213,11 @@ fn main() {
           let pix_x = sprite_x + (x % tile_size);
           let pix_y = sprite_y + (y % tile_size);
 
-          let pixel = texture.get_pixel(pix_x as u32, pix_y as u32);
-          imgbuf.put_pixel(x as u32, y as u32, pixel);
+          let pixel = texture.get_pixel(pix_x, pix_y);
+          imgbuf.put_pixel(x, y, pixel);
         }
       }
     }
 
-    // Save the image as output.png
-    imgbuf.save("output.png").unwrap()
+    imgbuf
 }